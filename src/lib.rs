@@ -13,7 +13,7 @@
 //! generic three-dimensional vector type, which should work well with
 //! `dimensioned`.
 //!
-//! Features: serde1, auto-args, clapme
+//! Features: serde1, auto-args, clapme, rand, mint, bytemuck
 
 #[cfg(feature = "serde1")]
 #[macro_use]
@@ -31,6 +31,7 @@ use std::fmt::Alignment;
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "clapme", derive(ClapMe))]
 #[cfg_attr(feature = "auto-args", derive(AutoArgs))]
+#[repr(C)]
 pub struct Vector3d<T> {
     /// The x component of the vector.
     pub x: T,
@@ -40,6 +41,14 @@ pub struct Vector3d<T> {
     pub z: T,
 }
 
+// bytemuck's derive macros refuse generic structs, so these are
+// implemented by hand, bounded on `T: Pod`/`T: Zeroable` as the
+// request specifies.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Vector3d<T> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Vector3d<T> {}
+
 impl<T> Vector3d<T> {
     /// Create a new `Vector3d`.
     pub fn new(x: T, y: T, z: T) -> Vector3d<T> {
@@ -52,38 +61,328 @@ impl<T> Vector3d<T> {
     }
 }
 
-// impl Vector3d<f64> {
-//     pub fn ran(scale: f64) -> Vector3d<f64> {
-//         unsafe {
-//             let mut x = 2.0 * RAN.ran() - 1.0;
-//             let mut y = 2.0 * RAN.ran() - 1.0;
-//             let mut r2 = x * x + y * y;
-//             while r2 >= 1.0 || r2 == 0.0 {
-//                 x = 2.0 * RAN.ran() - 1.0;
-//                 y = 2.0 * RAN.ran() - 1.0;
-//                 r2 = x * x + y * y;
-//             }
-//             let mut fac = scale * (-2.0 * r2.ln() / r2).sqrt();
-//             let mut out = Vector3d {
-//                 x: x * fac,
-//                 y: y * fac,
-//                 z: 0.0,
-//             };
-
-//             x = 2.0 * RAN.ran() - 1.0;
-//             y = 2.0 * RAN.ran() - 1.0;
-//             r2 = x * x + y * y;
-//             while r2 >= 1.0 || r2 == 0.0 {
-//                 x = 2.0 * RAN.ran() - 1.0;
-//                 y = 2.0 * RAN.ran() - 1.0;
-//                 r2 = x * x + y * y;
-//             }
-//             fac = scale * (-2.0 * r2.ln() / r2).sqrt();
-//             out[2] = x * fac;
-//             out
-//         }
-//     }
-// }
+/// A trait for scalar types that support a square root, used as a
+/// bound for [`Vector3d::norm`].
+///
+/// The output is an associated type rather than `Self`, since the
+/// square root of a squared `dimensioned` quantity (e.g. an area) has
+/// different units than its input, and so is a distinct type.
+/// `num_traits::Float` can't express this, since it requires its
+/// `Output` to equal `Self`, which is why we use this narrower trait
+/// instead.
+pub trait Sqrt {
+    /// The type of the square root of `Self`.
+    type Output;
+    /// Returns the square root of `self`.
+    fn sqrt(self) -> Self::Output;
+}
+impl Sqrt for f32 {
+    type Output = f32;
+    fn sqrt(self) -> f32 {
+        f32::sqrt(self)
+    }
+}
+impl Sqrt for f64 {
+    type Output = f64;
+    fn sqrt(self) -> f64 {
+        f64::sqrt(self)
+    }
+}
+
+impl<T: Clone + Mul<T, Output = X>, X: Add<Output = X> + Sqrt> Vector3d<T> {
+    /// The norm (i.e. length) of the vector, which is the square
+    /// root of [`Vector3d::norm2`].
+    pub fn norm(self) -> X::Output {
+        self.norm2().sqrt()
+    }
+
+    /// A dimensionless unit vector pointing in the same direction as
+    /// `self`, found by dividing each component by `self.norm()`.
+    pub fn normalized<Ratio>(self) -> Vector3d<Ratio>
+    where
+        X::Output: Clone,
+        T: Div<X::Output, Output = Ratio>,
+    {
+        let n = self.clone().norm();
+        Vector3d::new(self.x / n.clone(), self.y / n.clone(), self.z / n)
+    }
+}
+
+#[test]
+fn norm_and_normalized_work() {
+    let v = Vector3d::new(3.0, 4.0, 0.0);
+    assert_eq!(v.norm(), 5.0);
+    assert_eq!(v.normalized(), Vector3d::new(0.6, 0.8, 0.0));
+}
+
+#[cfg(feature = "rand")]
+mod random {
+    //! Random `Vector3d<f64>` generation, built on the `rand` crate's
+    //! `Rng`/`Distribution` traits rather than a hidden global
+    //! generator.
+    use super::Vector3d;
+    use rand::distributions::Distribution;
+    use rand::Rng;
+
+    /// Draw one pair of independent N(0, `scale`²) deviates using the
+    /// Marsaglia polar (Box–Muller) method.
+    fn polar_gaussian_pair<R: Rng + ?Sized>(rng: &mut R, scale: f64) -> (f64, f64) {
+        let mut x = 2.0 * rng.gen::<f64>() - 1.0;
+        let mut y = 2.0 * rng.gen::<f64>() - 1.0;
+        let mut r2 = x * x + y * y;
+        while r2 >= 1.0 || r2 == 0.0 {
+            x = 2.0 * rng.gen::<f64>() - 1.0;
+            y = 2.0 * rng.gen::<f64>() - 1.0;
+            r2 = x * x + y * y;
+        }
+        let fac = scale * (-2.0 * r2.ln() / r2).sqrt();
+        (x * fac, y * fac)
+    }
+
+    impl Vector3d<f64> {
+        /// Generate a vector whose three components are independent
+        /// Gaussian deviates with mean zero and standard deviation
+        /// `scale`.
+        pub fn gaussian<R: Rng + ?Sized>(rng: &mut R, scale: f64) -> Vector3d<f64> {
+            let (x, y) = polar_gaussian_pair(rng, scale);
+            let (z, _) = polar_gaussian_pair(rng, scale);
+            Vector3d::new(x, y, z)
+        }
+
+        /// Generate a uniformly distributed point on the unit sphere,
+        /// by normalizing a Gaussian vector.
+        pub fn on_unit_sphere<R: Rng + ?Sized>(rng: &mut R) -> Vector3d<f64> {
+            Vector3d::gaussian(rng, 1.0).normalized()
+        }
+    }
+
+    /// A distribution that samples Gaussian-distributed
+    /// `Vector3d<f64>` vectors with standard deviation 1, via
+    /// `rng.sample(StandardNormal3)`.
+    pub struct StandardNormal3;
+
+    impl Distribution<Vector3d<f64>> for StandardNormal3 {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector3d<f64> {
+            Vector3d::gaussian(rng, 1.0)
+        }
+    }
+
+    /// A distribution that samples uniformly distributed points on
+    /// the unit sphere, via `rng.sample(UnitSphere)`.
+    pub struct UnitSphere;
+
+    impl Distribution<Vector3d<f64>> for UnitSphere {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector3d<f64> {
+            Vector3d::on_unit_sphere(rng)
+        }
+    }
+}
+#[cfg(feature = "rand")]
+pub use random::{StandardNormal3, UnitSphere};
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_vectors_work() {
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+
+    let g = Vector3d::gaussian(&mut rng, 2.0);
+    assert!(g.x.is_finite() && g.y.is_finite() && g.z.is_finite());
+
+    let u = Vector3d::on_unit_sphere(&mut rng);
+    assert!((u.norm() - 1.0).abs() < 1e-9);
+
+    let g2: Vector3d<f64> = rng.sample(StandardNormal3);
+    assert!(g2.x.is_finite() && g2.y.is_finite() && g2.z.is_finite());
+
+    let u2: Vector3d<f64> = rng.sample(UnitSphere);
+    assert!((u2.norm() - 1.0).abs() < 1e-9);
+}
+
+/// A trait for approximate equality comparisons, for scalar and
+/// vector types whose `PartialEq` implementation is too strict to be
+/// useful with floating-point data.
+pub trait ApproxEq<Eps = Self> {
+    /// A reasonable default epsilon to use for `approx_eq`.
+    fn default_epsilon() -> Eps;
+    /// True if `self` and `other` differ by less than the default
+    /// epsilon.
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.approx_eq_eps(other, &Self::default_epsilon())
+    }
+    /// True if `self` and `other` differ by less than `eps`.
+    fn approx_eq_eps(&self, other: &Self, eps: &Eps) -> bool;
+}
+
+impl ApproxEq for f32 {
+    fn default_epsilon() -> f32 {
+        1e-6
+    }
+    fn approx_eq_eps(&self, other: &f32, eps: &f32) -> bool {
+        (self - other).abs() < *eps
+    }
+}
+
+impl ApproxEq for f64 {
+    fn default_epsilon() -> f64 {
+        1e-14
+    }
+    fn approx_eq_eps(&self, other: &f64, eps: &f64) -> bool {
+        (self - other).abs() < *eps
+    }
+}
+
+impl<T: ApproxEq> ApproxEq<T> for Vector3d<T> {
+    fn default_epsilon() -> T {
+        T::default_epsilon()
+    }
+    fn approx_eq_eps(&self, other: &Vector3d<T>, eps: &T) -> bool {
+        self.x.approx_eq_eps(&other.x, eps)
+            && self.y.approx_eq_eps(&other.y, eps)
+            && self.z.approx_eq_eps(&other.z, eps)
+    }
+}
+
+#[test]
+fn approx_eq_works() {
+    let a = Vector3d::new(1.0, 2.0, 3.0);
+    let b = Vector3d::new(1.0 + 1e-15, 2.0 - 1e-15, 3.0);
+    assert!(a.approx_eq(&b));
+    let c = Vector3d::new(1.1, 2.0, 3.0);
+    assert!(!a.approx_eq(&c));
+    assert!(a.approx_eq_eps(&c, &0.2));
+}
+
+impl<T: PartialOrd> Vector3d<T> {
+    /// The component-wise minimum of `self` and `other`.
+    pub fn min(self, other: Vector3d<T>) -> Vector3d<T> {
+        Vector3d::new(
+            if self.x < other.x { self.x } else { other.x },
+            if self.y < other.y { self.y } else { other.y },
+            if self.z < other.z { self.z } else { other.z },
+        )
+    }
+    /// The component-wise maximum of `self` and `other`.
+    pub fn max(self, other: Vector3d<T>) -> Vector3d<T> {
+        Vector3d::new(
+            if self.x > other.x { self.x } else { other.x },
+            if self.y > other.y { self.y } else { other.y },
+            if self.z > other.z { self.z } else { other.z },
+        )
+    }
+    /// Clamps each component of `self` between the corresponding
+    /// components of `lo` and `hi`.
+    pub fn clamp(self, lo: Vector3d<T>, hi: Vector3d<T>) -> Vector3d<T> {
+        self.max(lo).min(hi)
+    }
+}
+
+/// A trait for the additive identity, used as a bound for
+/// [`Vector3d::abs`].
+pub trait Zero {
+    /// The additive identity.
+    fn zero() -> Self;
+}
+impl Zero for f32 {
+    fn zero() -> f32 {
+        0.0
+    }
+}
+impl Zero for f64 {
+    fn zero() -> f64 {
+        0.0
+    }
+}
+impl Zero for i32 {
+    fn zero() -> i32 {
+        0
+    }
+}
+impl Zero for i64 {
+    fn zero() -> i64 {
+        0
+    }
+}
+
+impl<T: Neg<Output = T> + PartialOrd + Zero> Vector3d<T> {
+    /// The component-wise absolute value of `self`.
+    pub fn abs(self) -> Vector3d<T> {
+        Vector3d::new(abs_one(self.x), abs_one(self.y), abs_one(self.z))
+    }
+}
+fn abs_one<T: Neg<Output = T> + PartialOrd + Zero>(v: T) -> T {
+    if v < T::zero() {
+        -v
+    } else {
+        v
+    }
+}
+
+/// A trait for rounding scalar types, used as a bound for
+/// [`Vector3d::floor`], [`Vector3d::ceil`] and [`Vector3d::round`].
+pub trait Round {
+    /// Rounds down to the nearest integer.
+    fn floor(self) -> Self;
+    /// Rounds up to the nearest integer.
+    fn ceil(self) -> Self;
+    /// Rounds to the nearest integer, rounding half-way cases away
+    /// from zero.
+    fn round(self) -> Self;
+}
+impl Round for f32 {
+    fn floor(self) -> f32 {
+        f32::floor(self)
+    }
+    fn ceil(self) -> f32 {
+        f32::ceil(self)
+    }
+    fn round(self) -> f32 {
+        f32::round(self)
+    }
+}
+impl Round for f64 {
+    fn floor(self) -> f64 {
+        f64::floor(self)
+    }
+    fn ceil(self) -> f64 {
+        f64::ceil(self)
+    }
+    fn round(self) -> f64 {
+        f64::round(self)
+    }
+}
+
+impl<T: Round> Vector3d<T> {
+    /// The component-wise floor of `self`.
+    pub fn floor(self) -> Vector3d<T> {
+        Vector3d::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+    /// The component-wise ceiling of `self`.
+    pub fn ceil(self) -> Vector3d<T> {
+        Vector3d::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+    /// The component-wise rounding of `self`.
+    pub fn round(self) -> Vector3d<T> {
+        Vector3d::new(self.x.round(), self.y.round(), self.z.round())
+    }
+}
+
+#[test]
+fn min_max_clamp_abs_round_work() {
+    let a = Vector3d::new(1.0, -2.0, 3.5);
+    let b = Vector3d::new(-1.0, 2.0, 0.5);
+    assert_eq!(a.min(b), Vector3d::new(-1.0, -2.0, 0.5));
+    assert_eq!(a.max(b), Vector3d::new(1.0, 2.0, 3.5));
+    assert_eq!(
+        a.clamp(Vector3d::new(0.0, 0.0, 0.0), Vector3d::new(1.0, 1.0, 1.0)),
+        Vector3d::new(1.0, 0.0, 1.0)
+    );
+    assert_eq!(a.abs(), Vector3d::new(1.0, 2.0, 3.5));
+    assert_eq!(a.floor(), Vector3d::new(1.0, -2.0, 3.0));
+    assert_eq!(a.ceil(), Vector3d::new(1.0, -2.0, 4.0));
+    assert_eq!(a.round(), Vector3d::new(1.0, -2.0, 4.0));
+}
 
 /// These three operators (`Add`, `Sub`, and `Neg`) do not change
 /// units, and so we can implement them expecting type `T` to not
@@ -169,6 +468,55 @@ impl<S: Clone, X, T: Div<S, Output = X>> Div<S> for Vector3d<T> {
     }
 }
 
+use std::ops::AddAssign;
+impl<T: AddAssign<T>> AddAssign<Vector3d<T>> for Vector3d<T> {
+    fn add_assign(&mut self, rhs: Vector3d<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+use std::ops::SubAssign;
+impl<T: SubAssign<T>> SubAssign<Vector3d<T>> for Vector3d<T> {
+    fn sub_assign(&mut self, rhs: Vector3d<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+use std::ops::MulAssign;
+impl<S: Clone, T: MulAssign<S>> MulAssign<S> for Vector3d<T> {
+    fn mul_assign(&mut self, rhs: S) {
+        self.x *= rhs.clone();
+        self.y *= rhs.clone();
+        self.z *= rhs;
+    }
+}
+
+use std::ops::DivAssign;
+impl<S: Clone, T: DivAssign<S>> DivAssign<S> for Vector3d<T> {
+    fn div_assign(&mut self, rhs: S) {
+        self.x /= rhs.clone();
+        self.y /= rhs.clone();
+        self.z /= rhs;
+    }
+}
+
+#[test]
+fn assign_ops_work() {
+    let mut v = Vector3d::new(1.0, 2.0, 3.0);
+    v += Vector3d::new(1.0, 1.0, 1.0);
+    assert_eq!(v, Vector3d::new(2.0, 3.0, 4.0));
+    v -= Vector3d::new(1.0, 1.0, 1.0);
+    assert_eq!(v, Vector3d::new(1.0, 2.0, 3.0));
+    v *= 2.0;
+    assert_eq!(v, Vector3d::new(2.0, 4.0, 6.0));
+    v /= 2.0;
+    assert_eq!(v, Vector3d::new(1.0, 2.0, 3.0));
+}
+
 impl<T: Clone> Vector3d<T> {
     /// The cross product of two vectors.  Note that we assume that
     /// the components of both vector types have commutative
@@ -192,6 +540,155 @@ impl<T: Clone + Mul<T, Output = X>, X: Add<Output = X>> Vector3d<T> {
     }
 }
 
+impl<T: Clone + Add<Output = T> + Sub<Output = T> + Mul<T, Output = T>> Vector3d<T> {
+    /// Linearly interpolates between `self` (at `t == 0`) and `other`
+    /// (at `t == 1`).
+    pub fn lerp(self, other: Vector3d<T>, t: T) -> Vector3d<T> {
+        self.clone() + (other - self) * t
+    }
+
+    /// Reflects `self` about the plane whose unit normal is `normal`.
+    pub fn reflect(self, normal: Vector3d<T>) -> Vector3d<T> {
+        let d = self.clone().dot(normal.clone());
+        let two_d = d.clone() + d;
+        self - normal * two_d
+    }
+}
+
+impl<T: Clone + Add<Output = T> + Mul<T, Output = T> + Div<Output = T>> Vector3d<T> {
+    /// Projects `self` onto `onto`, returning the component of
+    /// `self` that is parallel to `onto`.
+    pub fn project_onto(self, onto: Vector3d<T>) -> Vector3d<T> {
+        let scale = self.dot(onto.clone()) / onto.clone().norm2();
+        onto * scale
+    }
+}
+
+impl Vector3d<f64> {
+    /// The angle between `self` and `other`, in radians.
+    ///
+    /// Computed as `atan2(|self × other|, self · other)` rather than
+    /// `acos(dot / (|self| |other|))`, since the latter loses
+    /// precision for angles near 0 or π.
+    pub fn angle_between(self, other: Vector3d<f64>) -> f64 {
+        self.cross(other).norm().atan2(self.dot(other))
+    }
+}
+
+#[test]
+fn geometry_helpers_work() {
+    let a = Vector3d::new(1.0, 0.0, 0.0);
+    let b = Vector3d::new(0.0, 1.0, 0.0);
+    assert_eq!(a.lerp(b, 0.5), Vector3d::new(0.5, 0.5, 0.0));
+    assert_eq!(a.reflect(b), a);
+    assert_eq!(a.reflect(a), -a);
+    assert_eq!(a.project_onto(Vector3d::new(2.0, 0.0, 0.0)), a);
+    assert!((a.angle_between(b) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    assert!(a.angle_between(a).abs() < 1e-12);
+}
+
+#[cfg(feature = "mint")]
+impl<T> From<mint::Vector3<T>> for Vector3d<T> {
+    fn from(v: mint::Vector3<T>) -> Vector3d<T> {
+        Vector3d::new(v.x, v.y, v.z)
+    }
+}
+#[cfg(feature = "mint")]
+impl<T> From<Vector3d<T>> for mint::Vector3<T> {
+    fn from(v: Vector3d<T>) -> mint::Vector3<T> {
+        mint::Vector3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+#[cfg(feature = "mint")]
+impl<T> From<mint::Point3<T>> for Vector3d<T> {
+    fn from(v: mint::Point3<T>) -> Vector3d<T> {
+        Vector3d::new(v.x, v.y, v.z)
+    }
+}
+#[cfg(feature = "mint")]
+impl<T> From<Vector3d<T>> for mint::Point3<T> {
+    fn from(v: Vector3d<T>) -> mint::Point3<T> {
+        mint::Point3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+#[test]
+fn mint_conversions_round_trip() {
+    let v = Vector3d::new(1.0, 2.0, 3.0);
+
+    let mv: mint::Vector3<f64> = v.into();
+    assert_eq!(Vector3d::from(mv), v);
+
+    let mp: mint::Point3<f64> = v.into();
+    assert_eq!(Vector3d::from(mp), v);
+}
+
+impl<T> From<[T; 3]> for Vector3d<T> {
+    fn from(a: [T; 3]) -> Vector3d<T> {
+        let [x, y, z] = a;
+        Vector3d::new(x, y, z)
+    }
+}
+impl<T> From<Vector3d<T>> for [T; 3] {
+    fn from(v: Vector3d<T>) -> [T; 3] {
+        [v.x, v.y, v.z]
+    }
+}
+impl<T> From<(T, T, T)> for Vector3d<T> {
+    fn from((x, y, z): (T, T, T)) -> Vector3d<T> {
+        Vector3d::new(x, y, z)
+    }
+}
+
+impl<T> Vector3d<T> {
+    /// Views `self` as a `&[T; 3]`, with no copy.
+    ///
+    /// This relies on `Vector3d` being `#[repr(C)]` with `x`, `y`,
+    /// `z` as its only fields, so it has the same layout as `[T; 3]`.
+    pub fn as_array(&self) -> &[T; 3] {
+        unsafe { &*(self as *const Self as *const [T; 3]) }
+    }
+    /// Views `self` as a `&mut [T; 3]`, with no copy.
+    pub fn as_mut_array(&mut self) -> &mut [T; 3] {
+        unsafe { &mut *(self as *mut Self as *mut [T; 3]) }
+    }
+}
+
+#[test]
+fn array_and_tuple_conversions_work() {
+    let v = Vector3d::new(1, 2, 3);
+    assert_eq!(Vector3d::from([1, 2, 3]), v);
+    assert_eq!(<[i32; 3]>::from(v), [1, 2, 3]);
+    assert_eq!(Vector3d::from((1, 2, 3)), v);
+
+    let mut v = Vector3d::new(1, 2, 3);
+    assert_eq!(v.as_array(), &[1, 2, 3]);
+    v.as_mut_array()[1] = 5;
+    assert_eq!(v, Vector3d::new(1, 5, 3));
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bytemuck_cast_slice_works() {
+    let vecs = [
+        Vector3d::new(1.0f32, 2.0, 3.0),
+        Vector3d::new(4.0f32, 5.0, 6.0),
+    ];
+    let bytes: &[u8] = bytemuck::cast_slice(&vecs);
+    assert_eq!(bytes.len(), 2 * 3 * std::mem::size_of::<f32>());
+    let back: &[Vector3d<f32>] = bytemuck::cast_slice(bytes);
+    assert_eq!(back, vecs);
+}
+
 use std::ops::Index;
 impl<T> Index<usize> for Vector3d<T> {
     type Output = T;